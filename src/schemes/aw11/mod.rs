@@ -29,10 +29,14 @@ extern crate serde_json;
 use std::string::String;
 use bn::*;
 use utils::policy::msp::AbePolicy;
-use utils::secretsharing::{gen_shares_str, calc_coefficients_str, calc_pruned_str};
+use utils::secretsharing::{gen_shares_str, gen_shares_str_committed, calc_coefficients_str, calc_pruned_str, lagrange_coefficients_at_zero};
+#[cfg(test)]
+use utils::secretsharing::verify_share;
 use utils::tools::*;
-use utils::aes::*;
 use utils::hash::blake2b_hash_g1;
+use utils::dkg;
+#[cfg(feature = "secure-erase")]
+use utils::secure_erase::volatile_zero;
 
 /// An AW11 Global Parameters Key (GK)
 #[derive(Serialize, Deserialize, PartialEq, Clone)]
@@ -53,6 +57,41 @@ pub struct Aw11MasterKey {
     pub _attr: Vec<(String, bn::Fr, bn::Fr)>,
 }
 
+/// Scrubs `_alpha_i`/`_y_i` from heap memory once the master key is dropped, so a process dump or
+/// reused allocation cannot leak them. Opt-in via the `secure-erase` feature, since downstream
+/// users who clone keys for serialization should not be surprised by eager zeroization.
+#[cfg(feature = "secure-erase")]
+impl Drop for Aw11MasterKey {
+    fn drop(&mut self) {
+        for _attr in self._attr.iter_mut() {
+            volatile_zero(&mut _attr.1);
+            volatile_zero(&mut _attr.2);
+        }
+    }
+}
+
+/// One committee member's additive share of an authority's Master Key, produced by
+/// `authgen_distributed`. Unlike `Aw11MasterKey`, a single `Aw11MasterKeyShare` does not hold a
+/// usable `_alpha_i`/`_y_i` for any attribute — only `_t` of them, combined with
+/// `utils::dkg::reconstruct`, recover the real master key.
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+pub struct Aw11MasterKeyShare {
+    pub _index: usize,
+    pub _attr: Vec<(String, bn::Fr, bn::Fr)>,
+}
+
+/// Scrubs this share's Shamir shares of `_alpha_i`/`_y_i` from heap memory once it is
+/// dropped, same as `Aw11MasterKey`'s `Drop` impl. Opt-in via the `secure-erase` feature.
+#[cfg(feature = "secure-erase")]
+impl Drop for Aw11MasterKeyShare {
+    fn drop(&mut self) {
+        for _attr in self._attr.iter_mut() {
+            volatile_zero(&mut _attr.1);
+            volatile_zero(&mut _attr.2);
+        }
+    }
+}
+
 /// An AW11 Ciphertext (CT)
 #[derive(Serialize, Deserialize, PartialEq, Clone)]
 pub struct Aw11Ciphertext {
@@ -60,6 +99,11 @@ pub struct Aw11Ciphertext {
     pub _c_0: bn::Gt,
     pub _c: Vec<(String, bn::Gt, bn::G2, bn::G2)>,
     pub _ct: Vec<u8>,
+    /// An optional NIZK proof, produced by `prove_encrypt`, that this ciphertext was honestly
+    /// formed. Absent on ciphertexts from plain `encrypt`, so older serialized ciphertexts
+    /// without a proof still deserialize.
+    #[serde(default)]
+    pub _proof: Option<Aw11EncryptProof>,
 }
 
 /// An AW11 Secret Key (SK)
@@ -69,6 +113,17 @@ pub struct Aw11SecretKey {
     pub _attr: Vec<(String, bn::G1)>,
 }
 
+/// Scrubs the GID-bound attribute key material from heap memory once the secret key is dropped.
+/// Opt-in via the `secure-erase` feature; see `Aw11MasterKey`'s `Drop` impl.
+#[cfg(feature = "secure-erase")]
+impl Drop for Aw11SecretKey {
+    fn drop(&mut self) {
+        for _attr in self._attr.iter_mut() {
+            volatile_zero(&mut _attr.1);
+        }
+    }
+}
+
 /// A global Context for an AW11 Global Parameters Key (GP)
 #[derive(Serialize, Deserialize, PartialEq, Clone)]
 pub struct Aw11GlobalContext {
@@ -136,6 +191,72 @@ pub fn authgen(
     return Some((Aw11PublicKey { _attr: _pk }, Aw11MasterKey { _attr: _sk }));
 }
 
+/// Aggregates a committee's DKG contributions into an authority's Public Key
+/// (PK) together with one Master Key share per committee member. No dealer
+/// picks an attribute's `_alpha_i`/`_y_i` alone: each is the sum of every
+/// dealer's constant term.
+///
+/// # Arguments
+///
+///	* `_gk` - A Global Parameters Key (GK), generated by the function setup()
+///	* `_attributes` - A Vector of String attributes assigned to this Authority
+///	* `_t` - The reconstruction threshold. At least `_t` committee members must cooperate to reconstruct a master-key value.
+///	* `_n` - The number of committee members jointly running the DKG round.
+///
+/// # Remarks
+///
+/// This runs a synchronous Pedersen/Feldman VSS round: every member deals a
+/// contribution for every attribute, the caller is expected to have already
+/// verified each contribution with `utils::dkg::verify_share` and dropped
+/// any that failed, and only the surviving contributions are aggregated
+/// here. The returned Master Key shares never reconstruct the real
+/// `Aw11MasterKey` by themselves; use `utils::dkg::reconstruct` with `_t`
+/// shares when `add_attribute` needs the full secret.
+///
+/// This function reads every dealer's private evaluations for every member
+/// and returns all `_n` shares, so whoever calls it holds enough shares to
+/// reconstruct the master key. `utils::dkg::aggregate` computes a single
+/// member's share from the same contributions.
+pub fn authgen_distributed(
+    _gk: &Aw11GlobalKey,
+    _attributes: &Vec<String>,
+    _t: usize,
+    _n: usize,
+    _contributions: &Vec<(String, Vec<dkg::DkgContribution>)>,
+) -> Option<(Aw11PublicKey, Vec<Aw11MasterKeyShare>)> {
+    if _attributes.is_empty() || _t == 0 || _t > _n {
+        return None;
+    }
+    let mut _pk: Vec<(String, bn::Gt, bn::G2)> = Vec::new();
+    let mut _shares: Vec<Vec<(String, bn::Fr, bn::Fr)>> = (1..=_n).map(|_| Vec::new()).collect();
+    for _attr in _attributes {
+        let _attr_upper = _attr.clone().to_uppercase();
+        let _attr_contributions = &_contributions
+            .iter()
+            .filter(|_c| _c.0 == _attr_upper)
+            .nth(0)?
+            .1;
+        if _attr_contributions.len() < _t {
+            return None;
+        }
+        for _member in 1..=_n {
+            let (_pk_alpha, _pk_y, _alpha_share, _y_share) =
+                dkg::aggregate(_gk._g1, _gk._g2, _member, _attr_contributions);
+            if _member == 1 {
+                _pk.push((_attr_upper.clone(), _pk_alpha, _pk_y));
+            }
+            _shares[_member - 1].push((_attr_upper.clone(), _alpha_share, _y_share));
+        }
+    }
+    let _msk_shares = (1..=_n)
+        .map(|_member| Aw11MasterKeyShare {
+            _index: _member,
+            _attr: _shares[_member - 1].clone(),
+        })
+        .collect();
+    return Some((Aw11PublicKey { _attr: _pk }, _msk_shares));
+}
+
 /// Sets up and generates a new User by creating a secret user key (SK). The key is created for a user with a given "name" on the given set of attributes.
 ///
 /// # Arguments
@@ -213,6 +334,51 @@ pub fn encrypt(
     _policy: &String,
     _plaintext: &[u8],
 ) -> Option<Aw11Ciphertext> {
+    let (mut _ct, _, _, _, _, _) = encrypt_internal(_gk, _pks, _policy, _plaintext)?;
+    _ct._proof = None;
+    return Some(_ct);
+}
+
+/// Like `encrypt`, but additionally attaches a NIZK proof (see `prove_encrypt`) that the returned
+/// ciphertext was honestly formed, so a verifier can reject a malformed ciphertext with
+/// `verify_encrypt` before attempting decryption.
+pub fn encrypt_with_proof(
+    _gk: &Aw11GlobalKey,
+    _pks: &Vec<Aw11PublicKey>,
+    _policy: &String,
+    _plaintext: &[u8],
+) -> Option<Aw11Ciphertext> {
+    let (mut _ct, _s, _m, _r, _shares, _commitments) =
+        encrypt_internal(_gk, _pks, _policy, _plaintext)?;
+    let _randomness = Aw11EncryptRandomness {
+        _s,
+        _m,
+        _r,
+        _shares,
+        _commitments,
+    };
+    _ct._proof = Some(prove_encrypt(_gk, _pks, _policy, &_ct, &_randomness));
+    return Some(_ct);
+}
+
+/// private function. shared by `encrypt` and `encrypt_with_proof`; also returns the secret `s`,
+/// the blinding exponent `m` (where `msg = e(g1,g2)^m`), the per-attribute randomizers `_r_x`,
+/// the MSP shares of `s` actually baked into `_c`, and the Feldman commitments to `s`'s sharing
+/// polynomial, so `encrypt_with_proof` can build a proof of correctness that is bound to the
+/// exact randomness and shares used.
+fn encrypt_internal(
+    _gk: &Aw11GlobalKey,
+    _pks: &Vec<Aw11PublicKey>,
+    _policy: &String,
+    _plaintext: &[u8],
+) -> Option<(
+    Aw11Ciphertext,
+    Fr,
+    Fr,
+    Vec<(String, Fr)>,
+    Vec<(String, Fr)>,
+    Vec<bn::G2>,
+)> {
     // random number generator
     let _rng = &mut rand::thread_rng();
     // an msp policy from the given String
@@ -221,14 +387,19 @@ pub fn encrypt(
     let _num_rows = msp._m.len();
     // pick randomness
     let _s = Fr::random(_rng);
-    // and calculate shares "s" and "zero"
-    let _s_shares = gen_shares_str(_s, _policy).unwrap();
+    // and calculate shares "s" (with Feldman commitments, so `prove_encrypt` can bind to them) and "zero"
+    let (_s_shares, _commitments) = gen_shares_str_committed(_s, _policy, _gk._g2).unwrap();
     let _w_shares = gen_shares_str(Fr::zero(), _policy).unwrap();
-    // calculate c0 with a randomly selected "msg"
-    let _msg = pairing(G1::random(_rng), G2::random(_rng));
+    // calculate c0 from a blinding exponent "m" the encryptor knows, so `prove_encrypt` can later
+    // prove c0's relation to s without ever revealing "m" or "s" themselves
+    let _m = Fr::random(_rng);
+    #[allow(unused_mut)]
+    let mut _msg = pairing(_gk._g1, _gk._g2).pow(_m);
     let _c_0 = _msg * pairing(_gk._g1, _gk._g2).pow(_s);
     // now calculate the C1,x C2,x and C3,x parts
     let mut _c: Vec<(String, bn::Gt, bn::G2, bn::G2)> = Vec::new();
+    let mut _r: Vec<(String, Fr)> = Vec::new();
+    let mut _shares: Vec<(String, Fr)> = Vec::new();
     for (_i, (_attr_name, _attr_share)) in _s_shares.into_iter().enumerate() {
         let _r_x = Fr::random(_rng);
         let _pk_attr = find_pk_attr(_pks, &_attr_name.to_uppercase()).unwrap();
@@ -239,16 +410,29 @@ pub fn encrypt(
             _gk._g2 * _r_x,
             (_pk_attr.2 * _r_x) + (_gk._g2 * _w_shares[_i].1),
         ));
+        _r.push((_attr_name.clone().to_uppercase(), _r_x));
+        _shares.push((_attr_name.clone().to_uppercase(), _attr_share));
     }
     //println!("enc: {:?}", serde_json::to_string(&_msg).unwrap());
     //Encrypt plaintext using derived key from secret
-    return Some(Aw11Ciphertext {
-        _policy: _policy.clone(),
-        _c_0: _c_0,
-        _c: _c,
-        _ct: encrypt_symmetric(&_msg, &_plaintext.to_vec()).unwrap(),
-    });
-
+    let _ciphertext = encrypt_symmetric(&_msg, &_plaintext.to_vec()).unwrap();
+    // scrub our only copy of the session key now that it has been used
+    #[cfg(feature = "secure-erase")]
+    volatile_zero(&mut _msg);
+    return Some((
+        Aw11Ciphertext {
+            _policy: _policy.clone(),
+            _c_0: _c_0,
+            _c: _c,
+            _ct: _ciphertext,
+            _proof: None,
+        },
+        _s,
+        _m,
+        _r,
+        _shares,
+        _commitments,
+    ));
 }
 
 /// This function decrypts a 'Aw11Ciphertext' if the attributes in SK match the policy of CT. If successfull, returns the plaintext data as a Vetor of u8's.
@@ -259,7 +443,28 @@ pub fn encrypt(
 ///	* `_sk` - A secret user key (SK), associated with a set of attributes.
 ///	* `_ct` - A Aw11Ciphertext
 pub fn decrypt(gk: &Aw11GlobalKey, sk: &Aw11SecretKey, ct: &Aw11Ciphertext) -> Option<Vec<u8>> {
-    let _str_attr = sk._attr
+    let _egg_s = compute_egg_s(gk, &sk._gid, &sk._attr, ct)?;
+    #[allow(unused_mut)]
+    let mut _msg = ct._c_0 * _egg_s.inverse();
+    // Decrypt plaintext using derived secret from cp-abe scheme
+    let _plaintext = decrypt_symmetric(&_msg, &ct._ct);
+    // scrub our only copy of the session key now that it has been used
+    #[cfg(feature = "secure-erase")]
+    volatile_zero(&mut _msg);
+    return _plaintext;
+}
+
+/// private function. runs the attribute-matching and pairing computation shared by `decrypt` and
+/// `partial_decrypt`, producing the `Gt` value that blinds `ct._c_0`. Takes the GID-bound key
+/// material as plain `(gid, attr)` pieces rather than a full `Aw11SecretKey` so it can run equally
+/// over a real secret key or over one committee member's `Aw11SecretKeyShare`.
+fn compute_egg_s(
+    gk: &Aw11GlobalKey,
+    _gid: &String,
+    _attr: &Vec<(String, bn::G1)>,
+    ct: &Aw11Ciphertext,
+) -> Option<Gt> {
+    let _str_attr = _attr
         .iter()
         .map(|_values| {
             let (_str, _g2) = _values.clone();
@@ -280,10 +485,10 @@ pub fn decrypt(gk: &Aw11GlobalKey, sk: &Aw11SecretKey, ct: &Aw11Ciphertext) -> O
                 let (_match, _list) = _p;
                 let _coeffs = calc_coefficients_str(&ct._policy).unwrap();
                 if _match {
-                    let _h_g1 = blake2b_hash_g1(gk._g1, &sk._gid);
+                    let _h_g1 = blake2b_hash_g1(gk._g1, _gid);
                     let mut _egg_s = Gt::one();
                     for _current in _list.iter() {
-                        let _sk_attr = sk._attr
+                        let _sk_attr = _attr
                             .iter()
                             .filter(|_attr| _attr.0 == _current.to_string())
                             .nth(0)
@@ -303,10 +508,7 @@ pub fn decrypt(gk: &Aw11GlobalKey, sk: &Aw11SecretKey, ct: &Aw11Ciphertext) -> O
                             .unwrap();
                         _egg_s = _egg_s * ((num * dem.inverse()).pow(_coeff));
                     }
-                    let _msg = ct._c_0 * _egg_s.inverse();
-                    //println!("dec: {:?}", serde_json::to_string(&_msg).unwrap());
-                    // Decrypt plaintext using derived secret from cp-abe scheme
-                    return decrypt_symmetric(&_msg, &ct._ct);
+                    return Some(_egg_s);
                 } else {
                     println!("Error: attributes in sk do not match policy in ct.");
                     return None;
@@ -315,6 +517,128 @@ pub fn decrypt(gk: &Aw11GlobalKey, sk: &Aw11SecretKey, ct: &Aw11Ciphertext) -> O
         }
     }
 }
+
+/// One committee member's `t`-of-`n` Shamir share of a user's secret key, dealt by `split_sk`.
+/// Unlike `Aw11SecretKey`, a single `Aw11SecretKeyShare` cannot decrypt anything on its own: its
+/// `_attr` components are points on a random degree-`(t-1)` polynomial over `G1` whose constant
+/// term is the real secret, so `partial_decrypt` takes this type rather than `Aw11SecretKey`.
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+pub struct Aw11SecretKeyShare {
+    pub _index: usize,
+    pub _gid: String,
+    pub _attr: Vec<(String, bn::G1)>,
+}
+
+/// Scrubs this share's GID-bound attribute key material from heap memory once it is dropped, same
+/// as `Aw11SecretKey`'s `Drop` impl: a single share cannot decrypt anything on its own, but `t` of
+/// them together can, so they deserve the same protection. Opt-in via the `secure-erase` feature.
+#[cfg(feature = "secure-erase")]
+impl Drop for Aw11SecretKeyShare {
+    fn drop(&mut self) {
+        for _attr in self._attr.iter_mut() {
+            volatile_zero(&mut _attr.1);
+        }
+    }
+}
+
+/// Splits `sk` into an `_n`-member, `_t`-threshold Shamir sharing of its GID-bound attribute key
+/// material, for use with `partial_decrypt`/`combine_shares`.
+///
+/// For each attribute, a random degree-`(_t-1)` polynomial over `G1` is sampled whose constant
+/// term is `sk`'s real secret component for that attribute; member `k`'s share is that polynomial
+/// evaluated at `k`. Because `G1` is an `Fr`-module, Lagrange interpolation at `x = 0` over any
+/// `_t` shares reconstructs the original component exactly as for a scalar secret, using the same
+/// coefficient machinery as `utils::secretsharing::lagrange_coefficients_at_zero`.
+pub fn split_sk(sk: &Aw11SecretKey, _t: usize, _n: usize) -> Vec<Aw11SecretKeyShare> {
+    let _rng = &mut rand::thread_rng();
+    let mut _shares: Vec<Vec<(String, bn::G1)>> = (0.._n).map(|_| Vec::new()).collect();
+    for (_attr_name, _secret) in sk._attr.iter() {
+        let mut _coeffs: Vec<bn::G1> = vec![*_secret];
+        for _ in 1.._t {
+            _coeffs.push(G1::random(_rng));
+        }
+        for _k in 1.._n + 1 {
+            let _k_fr = Fr::from_str(&_k.to_string()).unwrap();
+            let mut _share = G1::zero();
+            let mut _pow = Fr::one();
+            for _c in _coeffs.iter() {
+                _share = _share + (*_c * _pow);
+                _pow = _pow * _k_fr;
+            }
+            _shares[_k - 1].push((_attr_name.clone(), _share));
+        }
+    }
+    (1.._n + 1)
+        .map(|_k| Aw11SecretKeyShare {
+            _index: _k,
+            _gid: sk._gid.clone(),
+            _attr: _shares[_k - 1].clone(),
+        })
+        .collect()
+}
+
+/// A committee member's partial decryption of an `Aw11Ciphertext`, produced by `partial_decrypt`.
+/// `t` of these, combined with `combine_shares`, reconstruct the blinding element `_msg` without
+/// any single member learning it alone.
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+pub struct Aw11DecryptShare {
+    pub _index: usize,
+    pub _policy: String,
+    pub _egg_s_share: bn::Gt,
+}
+
+/// Computes one committee member's contribution towards decrypting a ciphertext, without
+/// revealing `_msg`. `_share` is this member's `Aw11SecretKeyShare`, dealt by `split_sk`, of the
+/// GID-bound attribute key material; this runs the same attribute-matching and pairing steps as
+/// `decrypt`, but stops at the per-attribute `Gt` product instead of unblinding `_c_0`.
+///
+/// # Arguments
+///
+///	* `gk` - A Global Parameters Key (GK), generated by setup()
+///	* `_share` - This member's Shamir share of the secret user key (SK), from `split_sk`
+///	* `ct` - A Aw11Ciphertext
+pub fn partial_decrypt(
+    gk: &Aw11GlobalKey,
+    _share: &Aw11SecretKeyShare,
+    ct: &Aw11Ciphertext,
+) -> Option<Aw11DecryptShare> {
+    let _egg_s_share = compute_egg_s(gk, &_share._gid, &_share._attr, ct)?;
+    Some(Aw11DecryptShare {
+        _index: _share._index,
+        _policy: ct._policy.clone(),
+        _egg_s_share,
+    })
+}
+
+/// Combines at least `t` distinct `Aw11DecryptShare`s into the plaintext, raising each member's
+/// `_egg_s_share` to its Lagrange coefficient `lambda_k = prod_{m != k} (0-m)/(k-m)` and
+/// multiplying the results to reconstruct `_egg_s`, mirroring how `decrypt` unblinds `_c_0` with a
+/// single key. Rejects the reconstruction if fewer than `_t` distinct member indices are present
+/// or if the shares do not all reference `ct`'s policy.
+pub fn combine_shares(
+    ct: &Aw11Ciphertext,
+    _shares: &Vec<Aw11DecryptShare>,
+    _t: usize,
+) -> Option<Vec<u8>> {
+    let mut _indices: Vec<usize> = _shares.iter().map(|_s| _s._index).collect();
+    _indices.sort();
+    _indices.dedup();
+    if _indices.len() < _t || _shares.iter().any(|_s| _s._policy != ct._policy) {
+        return None;
+    }
+    let _coeffs = lagrange_coefficients_at_zero(&_indices);
+    let mut _egg_s = Gt::one();
+    for _share in _shares.iter() {
+        let _lambda = _coeffs
+            .iter()
+            .filter(|_c| _c.0 == _share._index)
+            .map(|_c| _c.1)
+            .nth(0)?;
+        _egg_s = _egg_s * _share._egg_s_share.pow(_lambda);
+    }
+    let _msg = ct._c_0 * _egg_s.inverse();
+    return decrypt_symmetric(&_msg, &ct._ct);
+}
 /// private function. finds the value vector of a specific attribute in a vector of various public keys
 ///
 /// # Arguments
@@ -336,6 +660,364 @@ fn find_pk_attr(_pks: &Vec<Aw11PublicKey>, _attr: &String) -> Option<(String, bn
     return None;
 }
 
+/// A proxy re-encryption key letting a semi-trusted proxy turn an `Aw11Ciphertext` the delegator
+/// can decrypt into one a delegate holding a different attribute set can decrypt. Built by
+/// `gen_transform_key` and consumed by `transform`.
+///
+/// The per-attribute pairing product `compute_egg_s` reconstructs only cancels down to
+/// `e(g1,g2)^s` when it is evaluated with the delegator's real, unblinded key: the ciphertext-side
+/// numerator for an attribute carries a `pk_attr^r_x` term that has no counterpart the delegator
+/// could pre-blind by a factor `rk`, so blinding `_attr` itself (as an earlier version of this key
+/// did) leaves a ciphertext-dependent residual in `_egg_s_rk` instead of the exact
+/// `e(g1,g2)^(rk*s)` `decrypt_transformed` expects. `transform` therefore runs the real,
+/// unblinded reconstruction itself and only applies `rk` as a final, exact scalar exponentiation
+/// once the plaintext exponent is known — which means the semi-trusted proxy this key is handed to
+/// does learn the delegator's real GID-bound attribute components, on top of whatever ciphertexts
+/// it is asked to transform. Holding this key is therefore enough to decrypt every ciphertext the
+/// delegator can decrypt, so the plaintext is not hidden from the proxy.
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+pub struct Aw11TransformKey {
+    pub _gid: String,
+    /// the delegator's real, unblinded GID-bound key components
+    pub _attr: Vec<(String, bn::G1)>,
+    /// the delegate's Public Parameters Keys, used by `transform` to AW11-encrypt a fresh `rk` for
+    /// whichever ciphertext it is later asked to re-target
+    pub _delegate_pks: Vec<Aw11PublicKey>,
+    /// the JSON String policy describing the delegate's access rights
+    pub _delegate_policy: String,
+}
+
+/// The output of `transform`: a ciphertext re-targeted at the delegate's policy. The AES payload
+/// `_ct` is untouched; `_c_0`/`_egg_s_rk` carry the re-blinded pairing values the delegate strips
+/// in `decrypt_transformed` once it has recovered `rk` from `_rk_ct`.
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+pub struct Aw11TransformedCiphertext {
+    pub _rk_ct: Aw11Ciphertext,
+    pub _c_0: bn::Gt,
+    pub _egg_s_rk: bn::Gt,
+    pub _ct: Vec<u8>,
+}
+
+/// Builds a transform key letting a proxy turn ciphertexts `_delegator_sk` can decrypt into ones
+/// decryptable by whoever satisfies `_delegate_policy`.
+///
+/// # Arguments
+///
+///	* `_gk` - A Global Parameters Key (GK), generated by setup()
+///	* `_delegator_sk` - The delegator's secret user key (SK)
+///	* `_delegate_pks` - The Public Parameters Keys of the authorities covering `_delegate_policy`'s attributes
+///	* `_delegate_policy` - A JSON String policy describing the delegate's access rights
+pub fn gen_transform_key(
+    _gk: &Aw11GlobalKey,
+    _delegator_sk: &Aw11SecretKey,
+    _delegate_pks: &Vec<Aw11PublicKey>,
+    _delegate_policy: &String,
+) -> Option<Aw11TransformKey> {
+    Some(Aw11TransformKey {
+        _gid: _delegator_sk._gid.clone(),
+        _attr: _delegator_sk._attr.clone(),
+        _delegate_pks: _delegate_pks.clone(),
+        _delegate_policy: _delegate_policy.clone(),
+    })
+}
+
+/// Run by the semi-trusted proxy: re-targets `_ct` at the transform key's delegate policy. It
+/// reconstructs `e(g1,g2)^s`, the value that unblinds `_ct._c_0`, with the delegator's key exactly
+/// as `decrypt` does, so the proxy itself can recover the plaintext. The output does not carry that
+/// value: the proxy picks a fresh per-transform re-key factor `rk`, raises the reconstructed
+/// `e(g1,g2)^s` to it (exact, since `rk` is applied after reconstruction rather than folded into
+/// the pairing computation itself), and AW11-encrypts `rk` under the delegate's policy so only a
+/// delegate that recovers `rk` (via `decrypt_transformed`) can remove the blinding.
+///
+/// Fails, like `decrypt`, if the delegator's attributes baked into `_tk` do not satisfy `_ct`'s
+/// policy.
+pub fn transform(
+    _gk: &Aw11GlobalKey,
+    _tk: &Aw11TransformKey,
+    _ct: &Aw11Ciphertext,
+) -> Option<Aw11TransformedCiphertext> {
+    let _egg_s = compute_egg_s(_gk, &_tk._gid, &_tk._attr, _ct)?;
+    let _rng = &mut rand::thread_rng();
+    let _rk = Fr::random(_rng);
+    let _rk_ct = encrypt(
+        _gk,
+        &_tk._delegate_pks,
+        &_tk._delegate_policy,
+        &serde_json::to_vec(&_rk).unwrap(),
+    )?;
+    let _egg_s_rk = _egg_s.pow(_rk);
+    Some(Aw11TransformedCiphertext {
+        _rk_ct,
+        _c_0: _ct._c_0,
+        _egg_s_rk,
+        _ct: _ct._ct.clone(),
+    })
+}
+
+/// Finishes decrypting a ciphertext the proxy re-targeted with `transform`. The delegate first
+/// recovers `rk` by running ordinary `decrypt` against `_transformed._rk_ct` with its own
+/// `Aw11SecretKey` (which must satisfy the delegate policy embedded in the transform key), then
+/// uses `rk` to strip the `e(g1,g2)^(rk*s)` blinding down to `e(g1,g2)^s`, exactly reproducing
+/// what `decrypt` would have handed the delegator.
+///
+/// # Arguments
+///
+///	* `_gk` - A Global Parameters Key (GK), generated by setup()
+///	* `_delegate_sk` - The delegate's secret user key (SK)
+///	* `_transformed` - An Aw11TransformedCiphertext produced by `transform`
+pub fn decrypt_transformed(
+    _gk: &Aw11GlobalKey,
+    _delegate_sk: &Aw11SecretKey,
+    _transformed: &Aw11TransformedCiphertext,
+) -> Option<Vec<u8>> {
+    let _rk_bytes = decrypt(_gk, _delegate_sk, &_transformed._rk_ct)?;
+    let _rk: Fr = serde_json::from_slice(&_rk_bytes).ok()?;
+    let _egg_s = _transformed._egg_s_rk.pow(_rk.inverse()?);
+    let _msg = _transformed._c_0 * _egg_s.inverse();
+    return decrypt_symmetric(&_msg, &_transformed._ct);
+}
+
+/// The randomness `encrypt_internal` used to build a ciphertext: the shared secret `s`, the
+/// blinding exponent `m` (`msg = e(g1,g2)^m`), the per-attribute randomizers `_r_x`, the MSP
+/// shares of `s` actually baked into `_c`, and the Feldman commitments to `s`'s sharing
+/// polynomial. `prove_encrypt` needs these to prove the ciphertext's consistency without
+/// `verify_encrypt` ever learning them.
+pub struct Aw11EncryptRandomness {
+    pub _s: bn::Fr,
+    pub _m: bn::Fr,
+    pub _r: Vec<(String, bn::Fr)>,
+    pub _shares: Vec<(String, bn::Fr)>,
+    pub _commitments: Vec<bn::G2>,
+}
+
+/// A NIZK proof that an `Aw11Ciphertext` was honestly formed, directly bound to its public
+/// `_c_0`/`_c` values (not merely to a transcript hash of them): `verify_encrypt` checks pairing
+/// equations of the form `e(g1,g2)^z == T * ct_value^e` against `_ct._c_0` and each `_ct._c[i].1`,
+/// and a parallel `G2` equation tying every attribute's share to the same Feldman-committed secret
+/// `s` (reusing `utils::secretsharing`'s commit/verify machinery). Modeled as a Fiat-Shamir Sigma
+/// protocol, analogous to libbolt's `ProofCV` (commitment, challenge-blinded announcement,
+/// response), extended to a representation proof over two bases per equation.
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+pub struct Aw11EncryptProof {
+    /// Feldman commitments to the coefficients of `s`'s MSP sharing polynomial;
+    /// `_commitments[0] == g2^s`
+    pub _commitments: Vec<bn::G2>,
+    /// announcement tying `_z_m`/`_z_s` to `ct._c_0 == e(g1,g2)^(m+s)`
+    pub _t_0: bn::Gt,
+    /// announcement tying `_z_s` to `_commitments[0]`
+    pub _t_s: bn::G2,
+    /// per-attribute announcements: `(attr, T in Gt tied to ct._c[i].1, T in G2 tied to _commitments)`
+    pub _t_attr: Vec<(String, bn::Gt, bn::G2)>,
+    /// Fiat-Shamir challenge `e = H(policy, c_0, {c}, commitments, t_0, t_s, t_attr, |pks|)`, binding the
+    /// challenge to the prover's announcements as well as the public ciphertext values so a
+    /// forger cannot pick `T` after already knowing `e`
+    pub _e: bn::Fr,
+    /// response `z_m = blind_m + e*m`
+    pub _z_m: bn::Fr,
+    /// response `z_s = blind_s + e*s`
+    pub _z_s: bn::Fr,
+    /// per-attribute responses: `(attr, z_share = blind_share + e*share, z_{r_x} = blind_{r_x} + e*r_x)`
+    pub _z_attr: Vec<(String, bn::Fr, bn::Fr)>,
+}
+
+/// private function. derives the Fiat-Shamir challenge from the ciphertext's public values and
+/// the prover's announcement, reusing the crate's existing `blake2b` hash-to-`G1` primitive: the
+/// transcript is hashed to a `G1` point, whose serialized bytes are then folded directly into an
+/// `Fr` scalar via base-256 Horner reduction (every field operation already reduces mod the group
+/// order, so no separate bignum-mod step is needed). Deriving `e` this way uses the full output of
+/// the cryptographic hash, unlike folding it through a non-cryptographic hasher first.
+fn fiat_shamir_challenge(_gk: &Aw11GlobalKey, _transcript: &String) -> Fr {
+    let _point = blake2b_hash_g1(_gk._g1, _transcript);
+    let _bytes = serde_json::to_vec(&_point).unwrap();
+    let _base = Fr::from_str("256").unwrap();
+    let mut _e = Fr::zero();
+    for _byte in _bytes.iter() {
+        _e = (_e * _base) + Fr::from_str(&_byte.to_string()).unwrap();
+    }
+    _e
+}
+
+/// private function. finds the MSP row vector assigned to `_attr` (matched case-insensitively,
+/// since `_c`/`_r`/`_shares` store upper-cased attribute names while `AbePolicy::_pi` keeps the
+/// policy's original casing), as used by `gen_shares_str_committed` to derive that attribute's
+/// share of the secret.
+fn msp_row_for_attr(_msp: &AbePolicy, _attr: &str) -> Option<Vec<Fr>> {
+    _msp._pi
+        .iter()
+        .zip(_msp._m.iter())
+        .filter(|(_name, _)| _name.to_uppercase() == _attr)
+        .map(|(_, _row)| _row.clone())
+        .nth(0)
+}
+
+/// Proves that `_ct` (produced with the given `_randomness`) is a well-formed AW11 ciphertext,
+/// without revealing the shared secret `s`, the blinding exponent `m`, or any per-attribute
+/// randomizer `_r_x`/share.
+///
+/// # Arguments
+///
+///	* `_gk` - A Global Parameters Key (GK), generated by setup()
+///	* `_pks` - The Public Parameters Keys used to build `_ct`
+///	* `_policy` - The JSON String policy `_ct` was encrypted under
+///	* `_ct` - The Aw11Ciphertext to prove correctness of
+///	* `_randomness` - The `s`/`m`/`_r_x`/shares `encrypt_internal` used to build `_ct`
+pub fn prove_encrypt(
+    _gk: &Aw11GlobalKey,
+    _pks: &Vec<Aw11PublicKey>,
+    _policy: &String,
+    _ct: &Aw11Ciphertext,
+    _randomness: &Aw11EncryptRandomness,
+) -> Aw11EncryptProof {
+    let _rng = &mut rand::thread_rng();
+    let _blind_m = Fr::random(_rng);
+    let _blind_s = Fr::random(_rng);
+    let _t_0 = pairing(_gk._g1, _gk._g2).pow(_blind_m + _blind_s);
+    let _t_s = _gk._g2 * _blind_s;
+
+    let mut _t_attr: Vec<(String, Gt, bn::G2)> = Vec::new();
+    let mut _blinds: Vec<(String, Fr, Fr)> = Vec::new(); // (attr, blind_share, blind_rx)
+    for (_attr, _) in _randomness._shares.iter() {
+        let _pk_attr = find_pk_attr(_pks, _attr).unwrap();
+        let _blind_share = Fr::random(_rng);
+        let _blind_rx = Fr::random(_rng);
+        let _t_gt = pairing(_gk._g1, _gk._g2).pow(_blind_share) * _pk_attr.1.pow(_blind_rx);
+        let _t_g2 = _gk._g2 * _blind_share;
+        _t_attr.push((_attr.clone(), _t_gt, _t_g2));
+        _blinds.push((_attr.clone(), _blind_share, _blind_rx));
+    }
+
+    let _transcript = format!(
+        "{}{}{:?}{:?}{:?}{:?}{:?}{:?}",
+        _policy,
+        serde_json::to_string(&_ct._c_0).unwrap(),
+        _ct._c,
+        _randomness._commitments,
+        _t_0,
+        _t_s,
+        _t_attr,
+        _pks.len()
+    );
+    let _e = fiat_shamir_challenge(_gk, &_transcript);
+
+    let _z_m = _blind_m + (_e * _randomness._m);
+    let _z_s = _blind_s + (_e * _randomness._s);
+    let _z_attr = _randomness
+        ._shares
+        .iter()
+        .zip(_blinds.iter())
+        .map(|((_attr, _share), (_, _blind_share, _blind_rx))| {
+            let _r_x = _randomness
+                ._r
+                .iter()
+                .filter(|_r| _r.0 == *_attr)
+                .map(|_r| _r.1)
+                .nth(0)
+                .unwrap();
+            (
+                _attr.clone(),
+                *_blind_share + (_e * *_share),
+                *_blind_rx + (_e * _r_x),
+            )
+        })
+        .collect();
+
+    Aw11EncryptProof {
+        _commitments: _randomness._commitments.clone(),
+        _t_0,
+        _t_s,
+        _t_attr,
+        _e,
+        _z_m,
+        _z_s,
+        _z_attr,
+    }
+}
+
+/// Verifies a NIZK proof produced by `prove_encrypt`, rejecting a malformed ciphertext before a
+/// decrypting party wastes a pairing computation on it. Recomputes the challenge from `_ct`'s
+/// public values and the proof's commitments, then checks that the responses satisfy the
+/// announcement-response relation `base^z == T * target^e` for three real targets: `_ct._c_0`
+/// itself, each `_ct._c[i].1`, and the Feldman commitments' linear combination for that
+/// attribute's MSP row — so acceptance genuinely implies `_ct` was derived from a single
+/// consistent secret, not just that the prover can open its own fresh commitments. Also rejects a
+/// proof whose attribute set does not exactly match `_ct._c`'s, so an encryptor cannot omit an
+/// inconsistent entry from the proof and still have it accepted.
+pub fn verify_encrypt(
+    _gk: &Aw11GlobalKey,
+    _pks: &Vec<Aw11PublicKey>,
+    _policy: &String,
+    _ct: &Aw11Ciphertext,
+    _proof: &Aw11EncryptProof,
+) -> bool {
+    let _transcript = format!(
+        "{}{}{:?}{:?}{:?}{:?}{:?}{:?}",
+        _policy,
+        serde_json::to_string(&_ct._c_0).unwrap(),
+        _ct._c,
+        _proof._commitments,
+        _proof._t_0,
+        _proof._t_s,
+        _proof._t_attr,
+        _pks.len()
+    );
+    let _e = fiat_shamir_challenge(_gk, &_transcript);
+    if _e != _proof._e {
+        return false;
+    }
+    // ties z_m/z_s to the real c_0 that was published, not merely to a hash of it
+    if pairing(_gk._g1, _gk._g2).pow(_proof._z_m + _proof._z_s) !=
+        (_proof._t_0 * _ct._c_0.pow(_proof._e))
+    {
+        return false;
+    }
+    let _commit_s = match _proof._commitments.get(0) {
+        Some(_c) => *_c,
+        None => return false,
+    };
+    if (_gk._g2 * _proof._z_s) != (_proof._t_s + (_commit_s * _proof._e)) {
+        return false;
+    }
+    let _msp = match AbePolicy::from_string(_policy) {
+        Some(_msp) => _msp,
+        None => return false,
+    };
+    // every ct._c entry must have a matching proof entry, or a dishonest encryptor could omit an
+    // inconsistent entry from the proof's attribute list and still have it verify
+    let mut _proof_attrs: Vec<String> = _proof._z_attr.iter().map(|_a| _a.0.clone()).collect();
+    let mut _ct_attrs: Vec<String> = _ct._c.iter().map(|_c| _c.0.clone()).collect();
+    _proof_attrs.sort();
+    _ct_attrs.sort();
+    if _proof_attrs != _ct_attrs {
+        return false;
+    }
+    for (_attr, _z_share, _z_rx) in _proof._z_attr.iter() {
+        let _t = _proof._t_attr.iter().filter(|_t| _t.0 == *_attr).nth(0);
+        let _ct_attr = _ct._c.iter().filter(|_c| _c.0 == *_attr).nth(0);
+        let _pk_attr = find_pk_attr(_pks, _attr);
+        let _row = msp_row_for_attr(&_msp, _attr);
+        match (_t, _ct_attr, _pk_attr, _row) {
+            (Some(_t), Some(_ct_attr), Some(_pk_attr), Some(_row)) => {
+                // ties z_share/z_rx to the real ct._c[i].1 that was published
+                if (pairing(_gk._g1, _gk._g2).pow(*_z_share) * _pk_attr.1.pow(*_z_rx)) !=
+                    (_t.1 * _ct_attr.1.pow(_proof._e))
+                {
+                    return false;
+                }
+                // ties the same z_share to the Feldman commitments of the single committed secret
+                let mut _combined = G2::zero();
+                for (_m_ij, _commitment_j) in _row.iter().zip(_proof._commitments.iter()) {
+                    _combined = _combined + (*_commitment_j * *_m_ij);
+                }
+                if (_gk._g2 * *_z_share) != (_t.2 + (_combined * _proof._e)) {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+    }
+    true
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -390,4 +1072,167 @@ mod tests {
         let _matching = decrypt(&_gp, &_bob, &ct_cp).unwrap();
         assert_eq!(_matching, _plaintext);
     }
+
+    #[test]
+    fn test_authgen_distributed() {
+        let _gp = setup();
+        let _t = 2;
+        let _n = 3;
+        let _attributes = vec![String::from("A"), String::from("B")];
+
+        // every member deals a contribution for every attribute
+        let mut _contributions: Vec<(String, Vec<dkg::DkgContribution>)> = Vec::new();
+        for _attr in _attributes.iter() {
+            let _dealt: Vec<dkg::DkgContribution> = (1..=_n)
+                .map(|_from| dkg::deal(_gp._g1, _gp._g2, _from, _t, _n))
+                .collect();
+            // every member's evaluation checks out against the dealer's published commitments
+            for _contribution in _dealt.iter() {
+                for _to in 1..=_n {
+                    assert!(dkg::verify_share(_gp._g1, _gp._g2, _contribution, _to));
+                }
+            }
+            _contributions.push((_attr.clone(), _dealt));
+        }
+
+        let (_pk, _msk_shares) =
+            authgen_distributed(&_gp, &_attributes, _t, _n, &_contributions).unwrap();
+
+        // reconstruct the real master key from any `t` of the `n` committee shares
+        let mut _msk_attr: Vec<(String, Fr, Fr)> = Vec::new();
+        for _attr in _attributes.iter() {
+            let _shares: Vec<(usize, Fr, Fr)> = _msk_shares
+                .iter()
+                .take(_t)
+                .map(|_msk| {
+                    let _entry = _msk._attr.iter().filter(|_a| _a.0 == *_attr).nth(0).unwrap();
+                    (_msk._index, _entry.1, _entry.2)
+                })
+                .collect();
+            let (_alpha, _y) = dkg::reconstruct(&_shares);
+            _msk_attr.push((_attr.clone(), _alpha, _y));
+        }
+        let _msk = Aw11MasterKey { _attr: _msk_attr };
+
+        let _bob = keygen(&_gp, &_msk, &String::from("bob"), &_attributes).unwrap();
+        let _plaintext = String::from("distributed keygen round-trip").into_bytes();
+        let _policy = String::from(r#"{"AND": [{"ATT": "A"}, {"ATT": "B"}]}"#);
+        let _ct = encrypt(&_gp, &vec![_pk], &_policy, &_plaintext).unwrap();
+        let _matching = decrypt(&_gp, &_bob, &_ct).unwrap();
+        assert_eq!(_matching, _plaintext);
+    }
+
+    #[test]
+    fn test_threshold_decryption() {
+        let _gp = setup();
+        let _attributes = vec![String::from("A"), String::from("B")];
+        let (_pk, _msk) = authgen(&_gp, &_attributes).unwrap();
+        let _bob = keygen(&_gp, &_msk, &String::from("bob"), &_attributes).unwrap();
+        let _plaintext = String::from("threshold committee decryption").into_bytes();
+        let _policy = String::from(r#"{"AND": [{"ATT": "A"}, {"ATT": "B"}]}"#);
+        let _ct = encrypt(&_gp, &vec![_pk], &_policy, &_plaintext).unwrap();
+
+        // split bob's key into a 2-of-3 Shamir sharing
+        let _shares = split_sk(&_bob, 2, 3);
+        assert_eq!(_shares.len(), 3);
+
+        // any 2 of the 3 members' partial decryptions combine to the plaintext
+        let _partials: Vec<Aw11DecryptShare> = _shares
+            .iter()
+            .take(2)
+            .map(|_share| partial_decrypt(&_gp, _share, &_ct).unwrap())
+            .collect();
+        let _combined = combine_shares(&_ct, &_partials, 2).unwrap();
+        assert_eq!(_combined, _plaintext);
+
+        // fewer than the threshold must not reconstruct
+        assert!(combine_shares(&_ct, &_partials[0..1].to_vec(), 2).is_none());
+    }
+
+    #[test]
+    fn test_feldman_committed_shares() {
+        let _gp = setup();
+        let _rng = &mut rand::thread_rng();
+        let _secret = Fr::random(_rng);
+        let _policy = String::from(r#"{"AND": [{"ATT": "A"}, {"ATT": "B"}]}"#);
+        let (_shares, _commitments) =
+            gen_shares_str_committed(_secret, &_policy, _gp._g2).unwrap();
+        let _msp = AbePolicy::from_string(&_policy).unwrap();
+
+        for (_attr, _share) in _shares.iter() {
+            let _row = _msp
+                ._pi
+                .iter()
+                .zip(_msp._m.iter())
+                .filter(|(_name, _)| *_name == _attr)
+                .map(|(_, _row)| _row.clone())
+                .nth(0)
+                .unwrap();
+            // a genuine share is consistent with the published commitments
+            assert!(verify_share(*_share, &_row, &_commitments, _gp._g2));
+            // a tampered share is rejected
+            assert!(!verify_share(*_share + Fr::one(), &_row, &_commitments, _gp._g2));
+        }
+    }
+
+    #[test]
+    fn test_transform() {
+        let _gp = setup();
+        let (_delegator_pk, _delegator_msk) =
+            authgen(&_gp, &vec![String::from("A")]).unwrap();
+        let (_delegate_pk, _delegate_msk) =
+            authgen(&_gp, &vec![String::from("C")]).unwrap();
+
+        let _delegator = keygen(&_gp, &_delegator_msk, &String::from("bob"), &vec![
+            String::from("A"),
+        ]).unwrap();
+        let _delegate = keygen(&_gp, &_delegate_msk, &String::from("alice"), &vec![
+            String::from("C"),
+        ]).unwrap();
+
+        let _delegate_policy = String::from(r#"{"ATT": "C"}"#);
+        let _tk = gen_transform_key(
+            &_gp,
+            &_delegator,
+            &vec![_delegate_pk],
+            &_delegate_policy,
+        ).unwrap();
+
+        let _plaintext = String::from("proxy re-encryption round-trip").into_bytes();
+        let _delegator_policy = String::from(r#"{"ATT": "A"}"#);
+        let _ct = encrypt(&_gp, &vec![_delegator_pk], &_delegator_policy, &_plaintext).unwrap();
+
+        let _transformed = transform(&_gp, &_tk, &_ct).unwrap();
+        let _matching = decrypt_transformed(&_gp, &_delegate, &_transformed).unwrap();
+        assert_eq!(_matching, _plaintext);
+    }
+
+    #[test]
+    fn test_encrypt_proof() {
+        let _gp = setup();
+        let (_pk, _msk) = authgen(&_gp, &vec![String::from("A"), String::from("B")]).unwrap();
+        let _bob = keygen(&_gp, &_msk, &String::from("bob"), &vec![
+            String::from("A"),
+            String::from("B"),
+        ]).unwrap();
+        let _plaintext = String::from("proof of honest encryption").into_bytes();
+        let _policy = String::from(r#"{"AND": [{"ATT": "A"}, {"ATT": "B"}]}"#);
+
+        let _ct = encrypt_with_proof(&_gp, &vec![_pk.clone()], &_policy, &_plaintext).unwrap();
+        let _proof = _ct._proof.clone().unwrap();
+
+        // a genuine ciphertext's proof is accepted, and it still decrypts normally
+        assert!(verify_encrypt(&_gp, &vec![_pk.clone()], &_policy, &_ct, &_proof));
+        assert_eq!(decrypt(&_gp, &_bob, &_ct).unwrap(), _plaintext);
+
+        // a proof must not verify against a ciphertext whose c_0 was tampered with after the fact
+        let mut _tampered = _ct.clone();
+        _tampered._c_0 = _tampered._c_0 * _tampered._c_0;
+        assert!(!verify_encrypt(&_gp, &vec![_pk.clone()], &_policy, &_tampered, &_proof));
+
+        // nor against one whose per-attribute ciphertext entry was tampered with
+        let mut _tampered_attr = _ct.clone();
+        _tampered_attr._c[0].1 = _tampered_attr._c[0].1 * _tampered_attr._c[0].1;
+        assert!(!verify_encrypt(&_gp, &vec![_pk], &_policy, &_tampered_attr, &_proof));
+    }
 }
@@ -0,0 +1,159 @@
+//! Synchronous, dealerless distributed key generation (DKG) for attribute
+//! authority master keys.
+//!
+//! This implements a Pedersen/Feldman verifiable-secret-sharing round in the
+//! style of hbbft's `SyncKeyGen` and chain-vote's `DistributedKeyGeneration`:
+//! every committee member contributes a random polynomial share for each
+//! attribute, publishes verifiable commitments to its coefficients, and
+//! privately distributes evaluations to the other members. Once *t* honest
+//! contributions have been collected and verified, every member derives its
+//! own Shamir share (the sum of all dealers' evaluations at its index) of the
+//! attribute's `alpha`/`y` values without any single dealer choosing the full
+//! secret; any *t* such shares recover it by Lagrange interpolation at zero.
+extern crate bn;
+extern crate rand;
+
+use bn::{Fr, G2, Gt, pairing, Group};
+use utils::secretsharing::{lagrange_coefficients_at_zero};
+
+/// A single committee member's contribution to one attribute: the public
+/// Feldman commitments to its two polynomials (for `alpha_i` and `y_i`) and
+/// the private evaluations it owes every other member.
+#[derive(Clone)]
+pub struct DkgContribution {
+    /// index of the contributing member (1-based, as used for Lagrange interpolation)
+    pub _from: usize,
+    /// commitments `pairing(g1,g2)^coeff` to the coefficients of `f_j` (the `alpha` polynomial)
+    pub _commit_f: Vec<Gt>,
+    /// commitments `g2^coeff` to the coefficients of `h_j` (the `y` polynomial)
+    pub _commit_h: Vec<G2>,
+    /// private evaluations `f_j(k)` for every member `k`, indexed by `k - 1`
+    pub _shares_f: Vec<Fr>,
+    /// private evaluations `h_j(k)` for every member `k`, indexed by `k - 1`
+    pub _shares_h: Vec<Fr>,
+}
+
+/// Evaluates a polynomial given by its coefficients (constant term first) at `x`.
+fn eval_poly(_coeffs: &Vec<Fr>, _x: Fr) -> Fr {
+    let mut _acc = Fr::zero();
+    let mut _pow = Fr::one();
+    for _c in _coeffs.iter() {
+        _acc = _acc + (*_c * _pow);
+        _pow = _pow * _x;
+    }
+    _acc
+}
+
+/// Member `_from` samples two random degree-`(t-1)` polynomials for one
+/// attribute and prepares its public commitments plus the private shares it
+/// owes the other `_n` committee members.
+///
+/// # Arguments
+///
+/// * `_from` - the 1-based index of the contributing member
+/// * `_t` - the reconstruction threshold
+/// * `_n` - the total number of committee members
+pub fn deal(_gk_g1: bn::G1, _gk_g2: bn::G2, _from: usize, _t: usize, _n: usize) -> DkgContribution {
+    let _rng = &mut rand::thread_rng();
+    let _f_coeffs: Vec<Fr> = (0.._t).map(|_| Fr::random(_rng)).collect();
+    let _h_coeffs: Vec<Fr> = (0.._t).map(|_| Fr::random(_rng)).collect();
+    let _commit_f = _f_coeffs
+        .iter()
+        .map(|_c| pairing(_gk_g1, _gk_g2).pow(*_c))
+        .collect();
+    let _commit_h = _h_coeffs.iter().map(|_c| _gk_g2 * *_c).collect();
+    let _shares_f = (1..=_n)
+        .map(|_k| eval_poly(&_f_coeffs, Fr::from_str(&_k.to_string()).unwrap()))
+        .collect();
+    let _shares_h = (1..=_n)
+        .map(|_k| eval_poly(&_h_coeffs, Fr::from_str(&_k.to_string()).unwrap()))
+        .collect();
+    DkgContribution {
+        _from,
+        _commit_f,
+        _commit_h,
+        _shares_f,
+        _shares_h,
+    }
+}
+
+/// Verifies that the private evaluations `_contribution._shares_f[_to - 1]`
+/// and `_contribution._shares_h[_to - 1]` a member `_to` received from
+/// `_contribution._from` are consistent with the publicly broadcast
+/// commitments to both the `alpha` polynomial (`pairing(g1,g2) * f_j(k) ==
+/// prod(commit_fc ^ k^c)`) and the `y` polynomial (`g2 * h_j(k) ==
+/// sum(commit_hc * k^c)`). A member that fails either check must file a
+/// complaint and drop the dealer's contribution rather than use it — checking
+/// only the `y`/`_commit_h` side would let a dealer hand out an inconsistent
+/// `alpha` share undetected.
+pub fn verify_share(_gk_g1: bn::G1, _gk_g2: bn::G2, _contribution: &DkgContribution, _to: usize) -> bool {
+    let _k = Fr::from_str(&_to.to_string()).unwrap();
+    let _lhs_h = _gk_g2 * _contribution._shares_h[_to - 1];
+    let mut _rhs_h = G2::zero();
+    let mut _pow = Fr::one();
+    for _commit in _contribution._commit_h.iter() {
+        _rhs_h = _rhs_h + (*_commit * _pow);
+        _pow = _pow * _k;
+    }
+    if _lhs_h != _rhs_h {
+        return false;
+    }
+    let _lhs_f = pairing(_gk_g1, _gk_g2).pow(_contribution._shares_f[_to - 1]);
+    let mut _rhs_f = Gt::one();
+    let mut _pow = Fr::one();
+    for _commit in _contribution._commit_f.iter() {
+        _rhs_f = _rhs_f * _commit.pow(_pow);
+        _pow = _pow * _k;
+    }
+    _lhs_f == _rhs_f
+}
+
+/// Aggregates the verified contributions of (at least) `t` committee
+/// members into the authority's public values and this member's own master
+/// key share. Contributions that failed `verify_share` must already have
+/// been excluded from `_contributions` by the caller.
+///
+/// Returns `(pairing(g1,g2)^alpha, g2^y, alpha_share, y_share)` where the
+/// first two elements match today's `Aw11PublicKey` layout for this
+/// attribute and the latter two are this member's Shamir master-key share
+/// (the summed polynomials evaluated at `_me`).
+pub fn aggregate(
+    _gk_g1: bn::G1,
+    _gk_g2: bn::G2,
+    _me: usize,
+    _contributions: &Vec<DkgContribution>,
+) -> (Gt, G2, Fr, Fr) {
+    let mut _pk_alpha = pairing(_gk_g1, _gk_g2).pow(Fr::zero());
+    let mut _pk_y = _gk_g2 * Fr::zero();
+    let mut _alpha_share = Fr::zero();
+    let mut _y_share = Fr::zero();
+    for _contribution in _contributions.iter() {
+        _pk_alpha = _pk_alpha * _contribution._commit_f[0];
+        _pk_y = _pk_y + _contribution._commit_h[0];
+        _alpha_share = _alpha_share + _contribution._shares_f[_me - 1];
+        _y_share = _y_share + _contribution._shares_h[_me - 1];
+    }
+    (_pk_alpha, _pk_y, _alpha_share, _y_share)
+}
+
+/// Reconstructs the real `alpha_i`/`y_i` master-key values from any `t`
+/// committee members' shares, needed when `add_attribute` requires the full
+/// secret. Reuses the Lagrange-at-zero coefficient machinery shared with
+/// `utils::secretsharing`.
+pub fn reconstruct(_shares: &Vec<(usize, Fr, Fr)>) -> (Fr, Fr) {
+    let _indices: Vec<usize> = _shares.iter().map(|_s| _s.0).collect();
+    let _coeffs = lagrange_coefficients_at_zero(&_indices);
+    let mut _alpha = Fr::zero();
+    let mut _y = Fr::zero();
+    for (_index, _alpha_share, _y_share) in _shares.iter() {
+        let _lambda = _coeffs
+            .iter()
+            .filter(|_c| _c.0 == *_index)
+            .map(|_c| _c.1)
+            .nth(0)
+            .unwrap();
+        _alpha = _alpha + (*_alpha_share * _lambda);
+        _y = _y + (*_y_share * _lambda);
+    }
+    (_alpha, _y)
+}
@@ -0,0 +1,3 @@
+pub mod dkg;
+pub mod secretsharing;
+pub mod secure_erase;
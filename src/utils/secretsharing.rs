@@ -0,0 +1,77 @@
+extern crate bn;
+extern crate rand;
+
+use bn::{Fr, G2, Group};
+use utils::policy::msp::AbePolicy;
+
+/// Computes the Lagrange coefficients `lambda_k = prod_{m != k} (0 - m) / (k - m)`
+/// for reconstructing a secret at `x = 0` from the given set of distinct
+/// member indices. Shared by `utils::dkg` (master-key reconstruction) and
+/// threshold decryption, so both sides of a committee protocol interpolate
+/// the same way as the MSP-based secret sharing already used for policies.
+pub fn lagrange_coefficients_at_zero(_indices: &Vec<usize>) -> Vec<(usize, Fr)> {
+    _indices
+        .iter()
+        .map(|_k| {
+            let _k_fr = Fr::from_str(&_k.to_string()).unwrap();
+            let mut _num = Fr::one();
+            let mut _den = Fr::one();
+            for _m in _indices.iter() {
+                if _m != _k {
+                    let _m_fr = Fr::from_str(&_m.to_string()).unwrap();
+                    _num = _num * (Fr::zero() - _m_fr);
+                    _den = _den * (_k_fr - _m_fr);
+                }
+            }
+            (*_k, _num * _den.inverse().unwrap())
+        })
+        .collect()
+}
+
+/// Like `gen_shares_str`, but additionally returns Feldman commitments to the coefficients used
+/// to derive the shares, so a recipient who doesn't trust the dealer can check its share with
+/// `verify_share` before trusting it, with no extra round trips.
+///
+/// The commitments are `g2^secret` together with `g2^rho_j` for each random coefficient `rho_j`
+/// the MSP-based sharing uses internally; the share for an attribute's row `M_i` is still
+/// `sigma_i = M_i . (secret, rho_1, ...)` exactly as `gen_shares_str` computes it.
+pub fn gen_shares_str_committed(
+    _secret: Fr,
+    _policy: &String,
+    _g2: G2,
+) -> Option<(Vec<(String, Fr)>, Vec<G2>)> {
+    let _rng = &mut rand::thread_rng();
+    let _msp: AbePolicy = AbePolicy::from_string(_policy)?;
+    let _num_cols = _msp._m[0].len();
+    let mut _coeffs: Vec<Fr> = vec![_secret];
+    for _ in 1.._num_cols {
+        _coeffs.push(Fr::random(_rng));
+    }
+    let _commitments = _coeffs.iter().map(|_c| _g2 * *_c).collect();
+    let _shares = _msp
+        ._pi
+        .iter()
+        .zip(_msp._m.iter())
+        .map(|(_attr, _row)| {
+            let _share = _row
+                .iter()
+                .zip(_coeffs.iter())
+                .fold(Fr::zero(), |_acc, (_m_ij, _c_j)| _acc + (*_c_j * *_m_ij));
+            (_attr.clone(), _share)
+        })
+        .collect();
+    Some((_shares, _commitments))
+}
+
+/// Checks that a share `sigma_i` handed out for the MSP row vector `_row` is consistent with the
+/// published Feldman `_commitments`, i.e. that `g2 * sigma_i == sum_j(commitment_j * row[j])`.
+/// Lets a verifier confirm well-formedness of a single share without learning the shared secret
+/// or any other share.
+pub fn verify_share(_share: Fr, _row: &Vec<Fr>, _commitments: &Vec<G2>, _g2: G2) -> bool {
+    let _lhs = _g2 * _share;
+    let mut _rhs = G2::zero();
+    for (_m_ij, _commitment_j) in _row.iter().zip(_commitments.iter()) {
+        _rhs = _rhs + (*_commitment_j * *_m_ij);
+    }
+    _lhs == _rhs
+}
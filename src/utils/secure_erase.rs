@@ -0,0 +1,26 @@
+//! Best-effort secret scrubbing for types the `bn` curve crate does not
+//! implement `Zeroize` for (`Fr`, `G1`, `G2`, ...).
+//!
+//! Since we cannot rely on a `Zeroize` impl, this overwrites a value's raw
+//! in-memory representation directly with zero bytes, one byte at a time via
+//! `ptr::write_volatile` so the compiler cannot elide the writes as dead
+//! stores, followed by a compiler fence so later code cannot be reordered
+//! ahead of the scrub. This is gated behind the `secure-erase` feature; see
+//! the `Drop` impls in each scheme module for where it is used.
+use std::sync::atomic::{compiler_fence, Ordering};
+
+/// Overwrites the raw bytes backing `value` with zero. Safe to call on any
+/// `Sized` type, but only meaningful for plain-old-data types such as
+/// `bn::Fr`/`bn::G1`/`bn::G2` whose in-memory representation is their full
+/// secret state; calling it on a type holding a heap pointer (e.g. `Vec<u8>`)
+/// only scrubs the pointer/len/cap, not the backing allocation.
+pub fn volatile_zero<T>(value: &mut T) {
+    unsafe {
+        let _ptr = value as *mut T as *mut u8;
+        let _len = ::std::mem::size_of::<T>();
+        for _i in 0.._len {
+            ::std::ptr::write_volatile(_ptr.add(_i), 0u8);
+        }
+    }
+    compiler_fence(Ordering::SeqCst);
+}